@@ -1,20 +1,44 @@
 use anyhow::{Context, Result};
 use clap::Parser;
-use oshash::oshash;
-use std::path::PathBuf;
+use oshash::{oshash, oshash_or_full};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
 
 #[derive(Parser)]
 #[command(name = "oshash", version = "0.2.0", about = "A tool for hashing files using OSHash algorithm", long_about = None)]
 struct Cli {
     #[arg(short, long)]
     bench: bool,
-    /// Files to hash (default positional argument)
+    /// Walk the given paths recursively and write a manifest of path, size
+    /// and oshash digest to the provided file instead of printing hashes
+    #[arg(long, value_name = "MANIFEST")]
+    manifest: Option<PathBuf>,
+    /// Recompute hashes for the given paths and report changed/missing/new
+    /// entries relative to a previously written manifest
+    #[arg(long, value_name = "MANIFEST")]
+    verify: Option<PathBuf>,
+    /// Number of worker threads to fan file hashing across
+    #[arg(short, long, default_value_t = 1)]
+    jobs: usize,
+    /// Files (or, with --manifest/--verify, directories) to hash
     #[arg()]
     files: Vec<PathBuf>,
 }
 
 static COUNT: u32 = 1000;
 
+/// Bytes the oshash algorithm reads per file: the first and last 64KB.
+const BYTES_SEEKED_PER_FILE: u64 = 131_072;
+
+/// A single manifest record: the file's size in bytes and its oshash digest.
+struct ManifestEntry {
+    size: u64,
+    hash: String,
+}
+
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
@@ -23,30 +47,53 @@ fn main() -> Result<()> {
         return Err(anyhow::anyhow!("No files provided to hash"));
     }
 
+    if let Some(manifest_path) = cli.manifest {
+        return write_manifest(&files, &manifest_path);
+    }
+
+    if let Some(manifest_path) = cli.verify {
+        return verify_manifest(&files, &manifest_path);
+    }
+
     if cli.bench {
         let start = std::time::Instant::now();
         (0..COUNT - 1).for_each(|_| {
-            process_files(&files, false).expect("Failed to process files");
+            process_files(&files, false, cli.jobs).expect("Failed to process files");
         });
-        process_files(&files, true).expect("Failed to process files");
+        process_files(&files, true, cli.jobs).expect("Failed to process files");
 
         let duration = start.elapsed();
+        let total_files = files.len() as u64 * u64::from(COUNT);
+        let total_bytes_seeked = total_files * BYTES_SEEKED_PER_FILE;
+        let files_per_sec = total_files as f64 / duration.as_secs_f64();
+        let mb_seeked = total_bytes_seeked as f64 / (1024.0 * 1024.0);
+
         println!("Processed {} files 1000x in {:?}", files.len(), duration);
+        println!(
+            "Throughput: {files_per_sec:.1} files/sec, {:.1} MB/sec seeked ({mb_seeked:.1} MB total)",
+            mb_seeked / duration.as_secs_f64()
+        );
     } else {
-        process_files(&files, true)?;
+        process_files(&files, true, cli.jobs)?;
     }
 
     Ok(())
 }
-fn process_files(files: &[PathBuf], print: bool) -> Result<()> {
-    for file in files {
-        let hash = oshash(
-            file.as_os_str()
-                .to_str()
-                .context("could not convert to os_str")?,
-        )
-        .with_context(|| format!("Failed to hash file: {}", file.display()))?;
 
+fn process_files(files: &[PathBuf], print: bool, jobs: usize) -> Result<()> {
+    if jobs <= 1 {
+        for file in files {
+            let hash = hash_file(file)?;
+            if print {
+                println!("{hash} {}", file.display());
+            }
+        }
+
+        return Ok(());
+    }
+
+    for (file, result) in hash_files_parallel(files, jobs) {
+        let hash = result?;
         if print {
             println!("{hash} {}", file.display());
         }
@@ -54,3 +101,265 @@ fn process_files(files: &[PathBuf], print: bool) -> Result<()> {
 
     Ok(())
 }
+
+fn hash_file(file: &Path) -> Result<String> {
+    oshash(
+        file.as_os_str()
+            .to_str()
+            .context("could not convert to os_str")?,
+    )
+    .with_context(|| format!("Failed to hash file: {}", file.display()))
+}
+
+/// Hashes a file for manifest purposes using [`oshash_or_full`] rather than
+/// [`oshash`], so media libraries with files under 128KB don't abort the
+/// whole manifest walk with `FileTooSmall`.
+fn hash_for_manifest(file: &Path) -> Result<String> {
+    oshash_or_full(
+        file.as_os_str()
+            .to_str()
+            .context("could not convert to os_str")?,
+    )
+    .with_context(|| format!("Failed to hash file: {}", file.display()))
+}
+
+/// Hashes `files` across `jobs` worker threads, each pulling the next unhashed
+/// index from a shared counter, and returns `(path, result)` pairs in the
+/// original input order.
+fn hash_files_parallel(files: &[PathBuf], jobs: usize) -> Vec<(PathBuf, Result<String>)> {
+    let jobs = jobs.max(1).min(files.len().max(1));
+    let next_index = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<Result<String>>>> =
+        Mutex::new((0..files.len()).map(|_| None).collect());
+
+    std::thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| loop {
+                let index = next_index.fetch_add(1, Ordering::SeqCst);
+                if index >= files.len() {
+                    break;
+                }
+
+                let result = hash_file(&files[index]);
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .zip(files.iter().cloned())
+        .map(|(result, file)| (file, result.expect("every index is hashed exactly once")))
+        .collect()
+}
+
+/// Recursively collects every regular file under `root`, returning each
+/// file's path relative to `root` using `/`-separated components so
+/// manifests are portable across platforms.
+fn walk_relative(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut out = Vec::new();
+    walk_relative_into(root, Path::new(""), &mut out)?;
+    Ok(out)
+}
+
+fn walk_relative_into(root: &Path, relative: &Path, out: &mut Vec<PathBuf>) -> Result<()> {
+    let absolute = root.join(relative);
+
+    if absolute.is_dir() {
+        let mut entries: Vec<_> = fs::read_dir(&absolute)
+            .with_context(|| format!("Failed to read directory: {}", absolute.display()))?
+            .collect::<std::io::Result<Vec<_>>>()?;
+        entries.sort_by_key(std::fs::DirEntry::file_name);
+
+        for entry in entries {
+            walk_relative_into(root, &relative.join(entry.file_name()), out)?;
+        }
+    } else {
+        out.push(relative.to_path_buf());
+    }
+
+    Ok(())
+}
+
+/// Builds a manifest mapping each relative path under `roots` to its size
+/// and oshash digest, writing one `size\thash\tpath` record per line.
+fn write_manifest(roots: &[PathBuf], manifest_path: &Path) -> Result<()> {
+    let mut lines = Vec::new();
+
+    for root in roots {
+        for relative in walk_relative(root)? {
+            let absolute = root.join(&relative);
+            let size = fs::metadata(&absolute)
+                .with_context(|| format!("Failed to stat file: {}", absolute.display()))?
+                .len();
+            let hash = hash_for_manifest(&absolute)?;
+
+            lines.push(format!("{size}\t{hash}\t{}", relative.display()));
+        }
+    }
+
+    lines.sort();
+    fs::write(manifest_path, lines.join("\n") + "\n")
+        .with_context(|| format!("Failed to write manifest: {}", manifest_path.display()))?;
+
+    println!(
+        "Wrote manifest with {} entries to {}",
+        lines.len(),
+        manifest_path.display()
+    );
+
+    Ok(())
+}
+
+/// Parses a manifest file into `relative path -> ManifestEntry`.
+fn read_manifest(manifest_path: &Path) -> Result<BTreeMap<PathBuf, ManifestEntry>> {
+    let contents = fs::read_to_string(manifest_path)
+        .with_context(|| format!("Failed to read manifest: {}", manifest_path.display()))?;
+
+    let mut entries = BTreeMap::new();
+    for line in contents.lines() {
+        let mut parts = line.splitn(3, '\t');
+        let size = parts
+            .next()
+            .context("Malformed manifest entry: missing size")?
+            .parse::<u64>()
+            .context("Malformed manifest entry: invalid size")?;
+        let hash = parts
+            .next()
+            .context("Malformed manifest entry: missing hash")?
+            .to_string();
+        let path = parts
+            .next()
+            .context("Malformed manifest entry: missing path")?;
+
+        entries.insert(PathBuf::from(path), ManifestEntry { size, hash });
+    }
+
+    Ok(entries)
+}
+
+/// Recomputes hashes for every file under `roots` and reports entries that
+/// changed, went missing, or are new compared to `manifest_path`.
+fn verify_manifest(roots: &[PathBuf], manifest_path: &Path) -> Result<()> {
+    let previous = read_manifest(manifest_path)?;
+    let mut seen = BTreeMap::new();
+
+    for root in roots {
+        for relative in walk_relative(root)? {
+            let absolute = root.join(&relative);
+            let size = fs::metadata(&absolute)
+                .with_context(|| format!("Failed to stat file: {}", absolute.display()))?
+                .len();
+            let hash = hash_for_manifest(&absolute)?;
+
+            seen.insert(relative, ManifestEntry { size, hash });
+        }
+    }
+
+    let mut changed = 0;
+    let mut missing = 0;
+    let mut new = 0;
+
+    for (path, entry) in &seen {
+        match previous.get(path) {
+            Some(prev) if prev.hash == entry.hash && prev.size == entry.size => {}
+            Some(_) => {
+                changed += 1;
+                println!("changed {}", path.display());
+            }
+            None => {
+                new += 1;
+                println!("new {}", path.display());
+            }
+        }
+    }
+
+    for path in previous.keys() {
+        if !seen.contains_key(path) {
+            missing += 1;
+            println!("missing {}", path.display());
+        }
+    }
+
+    println!("{changed} changed, {missing} missing, {new} new");
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    /// A fresh scratch directory under the system temp dir, unique per test.
+    fn temp_dir(label: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let dir = std::env::temp_dir().join(format!(
+            "oshash-cli-test-{label}-{}-{nanos}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn manifest_round_trips_files_smaller_than_the_oshash_minimum() {
+        let root = temp_dir("manifest");
+        fs::write(root.join("small.txt"), b"short file content").unwrap();
+        fs::create_dir_all(root.join("nested")).unwrap();
+        fs::write(root.join("nested/other.txt"), b"more content").unwrap();
+
+        let manifest_path = root.join("manifest.txt");
+        write_manifest(&[root.clone()], &manifest_path).unwrap();
+
+        // No entry should be missing: oshash_or_full handles files under
+        // the 128KB oshash minimum instead of erroring the whole walk out.
+        let entries = read_manifest(&manifest_path).unwrap();
+        assert_eq!(entries.len(), 2);
+
+        // Verifying against itself reports no drift.
+        verify_manifest(&[root.clone()], &manifest_path).unwrap();
+
+        // Mutate a file's contents and add a new one; verify should see both.
+        fs::write(root.join("small.txt"), b"different content now").unwrap();
+        fs::write(root.join("new.txt"), b"brand new file").unwrap();
+        verify_manifest(&[root.clone()], &manifest_path).unwrap();
+
+        fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn parallel_hashing_matches_sequential_order_and_results() {
+        let root = temp_dir("parallel");
+        // `hash_file` uses plain `oshash`, which requires at least 128KB.
+        let filler = vec![0u8; 131_072];
+        let files: Vec<PathBuf> = (0..6)
+            .map(|i| {
+                let path = root.join(format!("file_{i}.txt"));
+                let mut contents = filler.clone();
+                contents[0] = i as u8;
+                fs::write(&path, contents).unwrap();
+                path
+            })
+            .collect();
+
+        let sequential: Vec<(PathBuf, String)> = files
+            .iter()
+            .map(|f| (f.clone(), hash_file(f).unwrap()))
+            .collect();
+
+        let parallel: Vec<(PathBuf, String)> = hash_files_parallel(&files, 4)
+            .into_iter()
+            .map(|(path, result)| (path, result.unwrap()))
+            .collect();
+
+        assert_eq!(sequential, parallel);
+
+        fs::remove_dir_all(&root).ok();
+    }
+}
@@ -0,0 +1,99 @@
+//! Async counterparts of [`crate::oshash`] and [`crate::oshash_buf`] for use
+//! inside async runtimes (e.g. an async server that should not block a
+//! worker thread on the two 64KB seeks/reads).
+//!
+//! This module is only available when the `async` feature is enabled, since
+//! it pulls in `tokio` as a dependency.
+use tokio::fs::File;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
+
+use crate::{to_uint64, HashError, CHUNK_SIZE};
+
+/// Hashes the file at the provided path identically to [`crate::oshash`],
+/// but performs its seeks and reads through `tokio`'s async I/O so it can be
+/// awaited inside an async runtime without blocking a worker thread.
+///
+/// # Errors
+///
+/// Will return `HashError::FileTooSmall` if the file is smaller than 128kb
+/// Will return any `IoError` surfaced from the filesystem
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() -> Result<(), oshash::HashError> {
+/// let result = oshash::oshash_async("test-resources/testdata").await?;
+///
+/// assert_eq!(result, "40d354daf3acce9c");
+/// # Ok(())
+/// # }
+/// ```
+///
+pub async fn oshash_async<T: AsRef<str>>(path: T) -> Result<String, HashError> {
+    let mut f = File::open(path.as_ref()).await?;
+    let len: u64 = f.metadata().await?.len();
+
+    oshash_buf_async(&mut f, len).await
+}
+
+/// Hashes an `AsyncRead + AsyncSeek` input if you already have a handle. If
+/// the input has an existing seek offset, then it will be reset back to that
+/// position when the function exits.
+///
+/// # Errors
+///
+/// Will return `HashError::FileTooSmall` if the file is smaller than 128kb
+/// Will return any `IoError` surfaced from the filesystem
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn run() -> Result<(), oshash::HashError> {
+/// let mut file = tokio::fs::File::open("test-resources/testdata").await?;
+/// let len = file.metadata().await?.len();
+/// let result = oshash::oshash_buf_async(&mut file, len).await?;
+///
+/// assert_eq!(result, "40d354daf3acce9c");
+/// # Ok(())
+/// # }
+/// ```
+///
+pub async fn oshash_buf_async<T>(file: &mut T, len: u64) -> Result<String, HashError>
+where
+    T: AsyncSeek + AsyncRead + Unpin,
+{
+    const MIN_FILE_SIZE: usize = (CHUNK_SIZE * 2) as usize;
+    if len < MIN_FILE_SIZE as u64 {
+        return Err(HashError::FileTooSmall);
+    }
+
+    let current_offset = file.stream_position().await?;
+
+    let mut file_hash: u64 = len;
+
+    let mut buffer = vec![0u8; CHUNK_SIZE as usize];
+
+    // Read first CHUNK_SIZE bytes
+    file.seek(std::io::SeekFrom::Start(0)).await?;
+    file.read_exact(&mut buffer).await?;
+
+    for chunk in buffer.chunks_exact(8) {
+        file_hash = file_hash.wrapping_add(u64::from_le_bytes(chunk.try_into().unwrap()));
+        to_uint64(&mut file_hash);
+    }
+
+    // Read last CHUNK_SIZE bytes
+    file.seek(std::io::SeekFrom::End(-(CHUNK_SIZE as i64)))
+        .await?;
+    file.read_exact(&mut buffer).await?;
+
+    for chunk in buffer.chunks_exact(8) {
+        file_hash = file_hash.wrapping_add(u64::from_le_bytes(chunk.try_into().unwrap()));
+        to_uint64(&mut file_hash);
+    }
+
+    // Restore original position
+    file.seek(std::io::SeekFrom::Start(current_offset)).await?;
+
+    Ok(format!("{file_hash:016x}"))
+}
@@ -9,11 +9,20 @@ use io::prelude::*;
 use std::fs::File;
 use std::{fmt, io};
 
-const CHUNK_SIZE: u64 = 65536;
+#[cfg(feature = "async")]
+mod async_hash;
+#[cfg(feature = "async")]
+pub use async_hash::{oshash_async, oshash_buf_async};
+
+pub(crate) const CHUNK_SIZE: u64 = 65536;
+const SAMPLE_SIZE: u64 = 4096;
 
 #[derive(Debug)]
 pub enum HashError {
     FileTooSmall,
+    InvalidChunkSize,
+    InvalidMinSizeMultiplier,
+    InvalidSampleCount,
     IoError(io::Error),
 }
 
@@ -21,6 +30,9 @@ impl fmt::Display for HashError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::FileTooSmall => write!(f, "File size too small"),
+            Self::InvalidChunkSize => write!(f, "Chunk size must be at least 1"),
+            Self::InvalidMinSizeMultiplier => write!(f, "Minimum size multiplier must be at least 1"),
+            Self::InvalidSampleCount => write!(f, "Sample count must be at least 1"),
             Self::IoError(err) => write!(f, "{err}"),
         }
     }
@@ -32,7 +44,7 @@ impl From<io::Error> for HashError {
     }
 }
 
-fn to_uint64(hash: &mut u64) {
+pub(crate) fn to_uint64(hash: &mut u64) {
     *hash &= 0xFFFF_FFFF_FFFF_FFFF;
 }
 
@@ -92,32 +104,223 @@ pub fn oshash_buf<T>(file: &mut T, len: u64) -> Result<String, HashError>
 where
     T: Seek + Read,
 {
-    const MIN_FILE_SIZE: usize = (CHUNK_SIZE * 2) as usize;
-    if len < MIN_FILE_SIZE as u64 {
+    OsHashOptions::default().oshash_buf(file, len)
+}
+
+/// Hashes the file at the provided path, mixing the file size with `n_samples`
+/// evenly spaced samples taken across the whole file rather than just the
+/// first and last 64KB. This catches interior mutations that leave the file
+/// size and the head/tail bytes unchanged, at the cost of a few extra seeks.
+///
+/// # Errors
+///
+/// Will return `HashError::InvalidSampleCount` if `n_samples` is 0
+/// Will return `HashError::FileTooSmall` if the file is smaller than the sample size
+/// Will return any `IoError` surfaced from the filesystem
+///
+/// # Example
+///
+/// ```
+/// let result = oshash::oshash_sampled("test-resources/testdata", 8).unwrap();
+/// ```
+///
+pub fn oshash_sampled<T: AsRef<str>>(path: T, n_samples: usize) -> Result<String, HashError> {
+    oshash_sampled_with_size(path, n_samples, SAMPLE_SIZE)
+}
+
+/// Hashes the file at the provided path using the sampled algorithm
+/// described in [`oshash_sampled`], but with a caller-provided sample size
+/// instead of the fixed 4KB default. A larger sample size reads more of the
+/// file per offset at the cost of fewer distinct offsets for the same I/O
+/// budget.
+///
+/// # Errors
+///
+/// Will return `HashError::InvalidSampleCount` if `n_samples` is 0
+/// Will return `HashError::FileTooSmall` if the file is smaller than `sample_size`
+/// Will return any `IoError` surfaced from the filesystem
+///
+/// # Example
+///
+/// ```
+/// let result = oshash::oshash_sampled_with_size("test-resources/testdata", 8, 4096).unwrap();
+/// ```
+///
+pub fn oshash_sampled_with_size<T: AsRef<str>>(
+    path: T,
+    n_samples: usize,
+    sample_size: u64,
+) -> Result<String, HashError> {
+    let mut f = File::open(path.as_ref())?;
+    let len: u64 = f.metadata()?.len();
+
+    oshash_buf_sampled_with_size(&mut f, len, n_samples, sample_size)
+}
+
+/// Hashes a `Read + Seek` input using the sampled algorithm described in
+/// [`oshash_sampled`]. If the file has an existing seek offset, then it will
+/// be reset back to that position when the function exits.
+///
+/// # Errors
+///
+/// Will return `HashError::InvalidSampleCount` if `n_samples` is 0
+/// Will return `HashError::FileTooSmall` if the file is smaller than the sample size
+/// Will return any `IoError` surfaced from the filesystem
+///
+/// # Example
+///
+/// ```
+/// let mut file = std::fs::File::open("test-resources/testdata").unwrap();
+/// let len = file.metadata().unwrap().len();
+/// let result = oshash::oshash_buf_sampled(&mut file, len, 8).unwrap();
+/// ```
+///
+pub fn oshash_buf_sampled<T>(file: &mut T, len: u64, n_samples: usize) -> Result<String, HashError>
+where
+    T: Seek + Read,
+{
+    oshash_buf_sampled_with_size(file, len, n_samples, SAMPLE_SIZE)
+}
+
+/// Hashes a `Read + Seek` input using the sampled algorithm described in
+/// [`oshash_sampled_with_size`]. If the file has an existing seek offset,
+/// then it will be reset back to that position when the function exits.
+///
+/// # Errors
+///
+/// Will return `HashError::InvalidSampleCount` if `n_samples` is 0
+/// Will return `HashError::FileTooSmall` if the file is smaller than `sample_size`
+/// Will return any `IoError` surfaced from the filesystem
+///
+/// # Example
+///
+/// ```
+/// let mut file = std::fs::File::open("test-resources/testdata").unwrap();
+/// let len = file.metadata().unwrap().len();
+/// let result = oshash::oshash_buf_sampled_with_size(&mut file, len, 8, 4096).unwrap();
+/// ```
+///
+pub fn oshash_buf_sampled_with_size<T>(
+    file: &mut T,
+    len: u64,
+    n_samples: usize,
+    sample_size: u64,
+) -> Result<String, HashError>
+where
+    T: Seek + Read,
+{
+    if n_samples < 1 {
+        return Err(HashError::InvalidSampleCount);
+    }
+
+    if sample_size < 1 {
+        return Err(HashError::InvalidChunkSize);
+    }
+
+    if len < sample_size {
         return Err(HashError::FileTooSmall);
     }
 
+    let max_slot = (len - sample_size) / sample_size;
+
     let current_offset = file.stream_position()?;
 
     let mut file_hash: u64 = len;
 
-    let mut buffer = vec![0u8; CHUNK_SIZE as usize];
+    let mut buffer = vec![0u8; sample_size as usize];
+
+    for i in 0..n_samples {
+        let slot = if n_samples == 1 {
+            0
+        } else {
+            (i as u64 * max_slot) / (n_samples as u64 - 1)
+        };
+        let offset = slot * sample_size;
+
+        file.seek(io::SeekFrom::Start(offset))?;
+        file.read_exact(&mut buffer)?;
+
+        for chunk in buffer.chunks_exact(8) {
+            file_hash = file_hash.wrapping_add(u64::from_le_bytes(chunk.try_into().unwrap()));
+            to_uint64(&mut file_hash);
+        }
+    }
+
+    // Restore original position
+    file.seek(io::SeekFrom::Start(current_offset))?;
+
+    Ok(format!("{file_hash:016x}"))
+}
+
+/// Hashes the file at the provided path the same way as [`oshash`], except
+/// that files smaller than 128KB are streamed through in full rather than
+/// rejected with `HashError::FileTooSmall`, so every file produces a
+/// deterministic digest through a single API.
+///
+/// # Errors
+///
+/// Will return any `IoError` surfaced from the filesystem
+///
+/// # Example
+///
+/// ```
+/// let result = oshash::oshash_or_full("test-resources/odd_length").unwrap();
+///
+/// assert_eq!(result, "840785078c0288af");
+/// ```
+///
+pub fn oshash_or_full<T: AsRef<str>>(path: T) -> Result<String, HashError> {
+    let mut f = File::open(path.as_ref())?;
+    let len: u64 = f.metadata()?.len();
+
+    oshash_buf_or_full(&mut f, len)
+}
 
-    // Read first CHUNK_SIZE bytes
+/// Hashes a `Read + Seek` input using the same fallback behavior as
+/// [`oshash_or_full`]. If the file has an existing seek offset, then it will
+/// be reset back to that position when the function exits.
+///
+/// # Errors
+///
+/// Will return any `IoError` surfaced from the filesystem
+///
+/// # Example
+///
+/// ```
+/// let mut file = std::fs::File::open("test-resources/odd_length").unwrap();
+/// let len = file.metadata().unwrap().len();
+/// let result = oshash::oshash_buf_or_full(&mut file, len).unwrap();
+///
+/// assert_eq!(result, "840785078c0288af");
+/// ```
+///
+pub fn oshash_buf_or_full<T>(file: &mut T, len: u64) -> Result<String, HashError>
+where
+    T: Seek + Read,
+{
+    if len >= 2 * CHUNK_SIZE {
+        return oshash_buf(file, len);
+    }
+
+    let current_offset = file.stream_position()?;
     file.seek(io::SeekFrom::Start(0))?;
-    file.read_exact(&mut buffer)?;
 
-    for chunk in buffer.chunks_exact(8) {
+    let mut file_hash: u64 = len;
+
+    let mut contents = Vec::with_capacity(len as usize);
+    file.by_ref().take(len).read_to_end(&mut contents)?;
+
+    let mut chunks = contents.chunks_exact(8);
+    for chunk in &mut chunks {
         file_hash = file_hash.wrapping_add(u64::from_le_bytes(chunk.try_into().unwrap()));
         to_uint64(&mut file_hash);
     }
 
-    // Read last CHUNK_SIZE bytes
-    file.seek(io::SeekFrom::End(-(CHUNK_SIZE as i64)))?;
-    file.read_exact(&mut buffer)?;
-
-    for chunk in buffer.chunks_exact(8) {
-        file_hash = file_hash.wrapping_add(u64::from_le_bytes(chunk.try_into().unwrap()));
+    let remainder = chunks.remainder();
+    if !remainder.is_empty() {
+        let mut padded = [0u8; 8];
+        padded[..remainder.len()].copy_from_slice(remainder);
+        file_hash = file_hash.wrapping_add(u64::from_le_bytes(padded));
         to_uint64(&mut file_hash);
     }
 
@@ -126,3 +329,130 @@ where
 
     Ok(format!("{file_hash:016x}"))
 }
+
+/// Builder for configuring the chunk size and minimum-size policy used by
+/// [`oshash`]/[`oshash_buf`]. Useful when hashing assets smaller than the
+/// 128KB the default 64KB chunk size requires, where a smaller chunk size
+/// still yields a stable OSHash-style digest.
+///
+/// # Example
+///
+/// ```
+/// let options = oshash::OsHashOptions::new().chunk_size(4096);
+/// let result = options.oshash("test-resources/small_file").unwrap();
+///
+/// assert_eq!(result, "0a0601fdf9f61328");
+/// ```
+///
+#[derive(Debug, Clone, Copy)]
+pub struct OsHashOptions {
+    chunk_size: u64,
+    min_size_multiplier: u64,
+}
+
+impl Default for OsHashOptions {
+    fn default() -> Self {
+        Self {
+            chunk_size: CHUNK_SIZE,
+            min_size_multiplier: 2,
+        }
+    }
+}
+
+impl OsHashOptions {
+    /// Creates a new builder with the default 64KB chunk size.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the chunk size, in bytes, read from the start and end of the
+    /// file. Must be at least 1; files smaller than
+    /// `min_size_multiplier * chunk_size` will return `HashError::FileTooSmall`.
+    #[must_use]
+    pub fn chunk_size(mut self, chunk_size: u64) -> Self {
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Sets the minimum-size policy: files smaller than
+    /// `min_size_multiplier * chunk_size` return `HashError::FileTooSmall`.
+    /// Defaults to 2, matching the original "first and last chunk must not
+    /// overlap" requirement. Set to 1 to allow files as small as a single
+    /// chunk, where the first and last chunk reads overlap.
+    #[must_use]
+    pub fn min_size_multiplier(mut self, min_size_multiplier: u64) -> Self {
+        self.min_size_multiplier = min_size_multiplier;
+        self
+    }
+
+    /// Hashes the file at the provided path using this builder's chunk size.
+    ///
+    /// # Errors
+    ///
+    /// Will return `HashError::InvalidChunkSize` if the chunk size is 0
+    /// Will return `HashError::InvalidMinSizeMultiplier` if the min size multiplier is 0
+    /// Will return `HashError::FileTooSmall` if the file is smaller than `min_size_multiplier * chunk_size`
+    /// Will return any `IoError` surfaced from the filesystem
+    pub fn oshash<T: AsRef<str>>(&self, path: T) -> Result<String, HashError> {
+        let mut f = File::open(path.as_ref())?;
+        let len: u64 = f.metadata()?.len();
+
+        self.oshash_buf(&mut f, len)
+    }
+
+    /// Hashes a `Read + Seek` input using this builder's chunk size. If the
+    /// file has an existing seek offset, then it will be reset back to that
+    /// position when the function exits.
+    ///
+    /// # Errors
+    ///
+    /// Will return `HashError::InvalidChunkSize` if the chunk size is 0
+    /// Will return `HashError::InvalidMinSizeMultiplier` if the min size multiplier is 0
+    /// Will return `HashError::FileTooSmall` if the file is smaller than `min_size_multiplier * chunk_size`
+    /// Will return any `IoError` surfaced from the filesystem
+    pub fn oshash_buf<T>(&self, file: &mut T, len: u64) -> Result<String, HashError>
+    where
+        T: Seek + Read,
+    {
+        let chunk_size = self.chunk_size;
+        if chunk_size < 1 {
+            return Err(HashError::InvalidChunkSize);
+        }
+        if self.min_size_multiplier < 1 {
+            return Err(HashError::InvalidMinSizeMultiplier);
+        }
+        if len < self.min_size_multiplier * chunk_size {
+            return Err(HashError::FileTooSmall);
+        }
+
+        let current_offset = file.stream_position()?;
+
+        let mut file_hash: u64 = len;
+
+        let mut buffer = vec![0u8; chunk_size as usize];
+
+        // Read first chunk_size bytes
+        file.seek(io::SeekFrom::Start(0))?;
+        file.read_exact(&mut buffer)?;
+
+        for chunk in buffer.chunks_exact(8) {
+            file_hash = file_hash.wrapping_add(u64::from_le_bytes(chunk.try_into().unwrap()));
+            to_uint64(&mut file_hash);
+        }
+
+        // Read last chunk_size bytes
+        file.seek(io::SeekFrom::End(-(chunk_size as i64)))?;
+        file.read_exact(&mut buffer)?;
+
+        for chunk in buffer.chunks_exact(8) {
+            file_hash = file_hash.wrapping_add(u64::from_le_bytes(chunk.try_into().unwrap()));
+            to_uint64(&mut file_hash);
+        }
+
+        // Restore original position
+        file.seek(io::SeekFrom::Start(current_offset))?;
+
+        Ok(format!("{file_hash:016x}"))
+    }
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "async")]
+
+use std::path::Path;
+use tokio::fs::File;
+use tokio::io::AsyncSeekExt;
+
+use oshash::{oshash_async, oshash_buf_async};
+
+#[tokio::test]
+async fn it_hashes_properly_async() {
+    let path = Path::new("test-resources/testdata")
+        .as_os_str()
+        .to_str()
+        .unwrap();
+    let result = oshash_async(path).await.unwrap();
+    assert_eq!(result, "40d354daf3acce9c");
+}
+
+#[tokio::test]
+async fn it_accepts_seek_and_confirms_seeks_and_leave_seek_at_original_offset_async() {
+    let mut file = File::open("test-resources/testdata").await.unwrap();
+    let len = file.metadata().await.unwrap().len();
+    let offset = 10;
+    file.seek(std::io::SeekFrom::Start(offset)).await.unwrap();
+    let result = oshash_buf_async(&mut file, len).await.unwrap();
+    assert_eq!(result, "40d354daf3acce9c");
+
+    assert_eq!(file.stream_position().await.unwrap(), offset);
+}
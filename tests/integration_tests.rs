@@ -2,7 +2,7 @@ use std::fs::File;
 use std::io::{self, Seek};
 use std::path::Path;
 
-use oshash::{oshash, oshash_buf, HashError};
+use oshash::{oshash, oshash_buf, oshash_or_full, oshash_sampled, HashError, OsHashOptions};
 
 #[test]
 fn it_hashes_properly() {
@@ -72,3 +72,103 @@ fn it_throws_error_when_input_too_small_for_buf() {
     assert!(result.is_err());
     assert_eq!(result.unwrap_err().to_string(), "File size too small");
 }
+
+#[test]
+fn it_hashes_with_a_smaller_configured_chunk_size() {
+    let path = Path::new("test-resources/small_file")
+        .as_os_str()
+        .to_str()
+        .unwrap();
+    let options = OsHashOptions::new().chunk_size(4096);
+    let result = options.oshash(path).unwrap();
+    assert_eq!(result, "0a0601fdf9f61328");
+}
+
+#[test]
+fn it_throws_error_when_chunk_size_is_zero() {
+    let path = Path::new("test-resources/small_file")
+        .as_os_str()
+        .to_str()
+        .unwrap();
+    let options = OsHashOptions::new().chunk_size(0);
+    let result = options.oshash(path);
+    assert!(result.is_err());
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Chunk size must be at least 1"
+    );
+}
+
+#[test]
+fn it_throws_error_when_min_size_multiplier_is_zero() {
+    let path = Path::new("test-resources/small_file")
+        .as_os_str()
+        .to_str()
+        .unwrap();
+    let options = OsHashOptions::new().chunk_size(4096).min_size_multiplier(0);
+    let result = options.oshash(path);
+    assert!(result.is_err());
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "Minimum size multiplier must be at least 1"
+    );
+}
+
+#[test]
+fn it_allows_a_single_chunk_file_with_min_size_multiplier_of_one() {
+    let path = Path::new("test-resources/small_file")
+        .as_os_str()
+        .to_str()
+        .unwrap();
+    let options = OsHashOptions::new().chunk_size(9000).min_size_multiplier(1);
+    let result = options.oshash(path);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn it_detects_interior_mutations_that_oshash_misses() {
+    let a = Path::new("test-resources/interior_a")
+        .as_os_str()
+        .to_str()
+        .unwrap();
+    let b = Path::new("test-resources/interior_b")
+        .as_os_str()
+        .to_str()
+        .unwrap();
+
+    // Same size, same first/last 64KB: oshash can't tell them apart.
+    assert_eq!(oshash(a).unwrap(), oshash(b).unwrap());
+
+    // The sampled mode reads evenly spaced interior offsets, so it does.
+    assert_ne!(
+        oshash_sampled(a, 8).unwrap(),
+        oshash_sampled(b, 8).unwrap()
+    );
+}
+
+#[test]
+fn it_hashes_a_file_too_small_for_oshash_via_full_fallback() {
+    let path = Path::new("test-resources/odd_length")
+        .as_os_str()
+        .to_str()
+        .unwrap();
+
+    // oshash rejects this file outright...
+    assert!(matches!(oshash(path), Err(HashError::FileTooSmall)));
+
+    // ...but oshash_or_full streams its full contents, zero-padding the
+    // trailing 3-byte group (1003 bytes is not a multiple of 8), and still
+    // produces a deterministic digest.
+    let result = oshash_or_full(path).unwrap();
+    assert_eq!(result, "840785078c0288af");
+}
+
+#[test]
+fn it_delegates_to_oshash_for_large_enough_files() {
+    let path = Path::new("test-resources/testdata")
+        .as_os_str()
+        .to_str()
+        .unwrap();
+
+    assert_eq!(oshash_or_full(path).unwrap(), oshash(path).unwrap());
+}